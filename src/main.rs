@@ -1,16 +1,30 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, SaltString},
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+};
 use axum::{
     async_trait,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{FromRequestParts, Path, State},
+    http::{header, request::Parts, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header as JwtHeader, Validation};
 #[allow(unused_imports)]
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use sqids::Sqids;
 use std::{net::SocketAddr, sync::Arc};
+use tower::ServiceBuilder;
+use tower_http::{
+    compression::CompressionLayer, cors::CorsLayer, decompression::RequestDecompressionLayer,
+    trace::TraceLayer,
+};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use utoipa::{OpenApi, ToSchema};
+use utoipa_swagger_ui::SwaggerUi;
 use uuid::Uuid;
 
 #[tokio::main]
@@ -23,10 +37,17 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    let config = Config::init();
     let user_repo = Arc::new(ExampleUserRepo) as DynUserRepo;
-    let app = app(user_repo);
+    let state = Arc::new(AppState { config, user_repo });
+
+    let addr: SocketAddr = state
+        .config
+        .bind_addr
+        .parse()
+        .expect("BIND_ADDR must be a valid socket address");
+    let app = app(state);
 
-    let addr = SocketAddr::from(([127, 0, 0, 1], 4444));
     tracing::debug!("listening on {}", addr);
     axum::Server::bind(&addr)
         .serve(app.into_make_service())
@@ -34,57 +55,370 @@ async fn main() {
         .unwrap();
 }
 
-fn app(user_repo: DynUserRepo) -> Router {
+struct AppState {
+    config: Config,
+    user_repo: DynUserRepo,
+}
+
+type SharedState = Arc<AppState>;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(users_show, users_create),
+    components(schemas(User, CreateUser, ErrorResponse)),
+    tags((name = "users", description = "User management endpoints"))
+)]
+struct ApiDoc;
+
+fn app(state: SharedState) -> Router {
+    let cors = cors_layer(&state.config);
+
     Router::new()
         .route("/users/:user_id", get(users_show))
         .route("/users", post(users_create))
-        .with_state(user_repo)
+        .route("/register", post(register))
+        .route("/login", post(login))
+        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .layer(
+            ServiceBuilder::new()
+                .layer(TraceLayer::new_for_http())
+                .layer(cors)
+                .layer(CompressionLayer::new())
+                .layer(RequestDecompressionLayer::new()),
+        )
+        .with_state(state)
 }
 
+fn cors_layer(config: &Config) -> CorsLayer {
+    // Headers are always unrestricted: no sensitive request headers to gate.
+    let mut cors = CorsLayer::new().allow_headers(tower_http::cors::Any);
+
+    cors = if config.cors_allowed_origins.iter().any(|origin| origin == "*") {
+        cors.allow_origin(tower_http::cors::Any)
+    } else {
+        let origins: Vec<HeaderValue> = config
+            .cors_allowed_origins
+            .iter()
+            .filter_map(|origin| origin.parse().ok())
+            .collect();
+        cors.allow_origin(origins)
+    };
+
+    if config.cors_allowed_methods.iter().any(|method| method == "*") {
+        cors.allow_methods(tower_http::cors::Any)
+    } else {
+        let methods: Vec<axum::http::Method> = config
+            .cors_allowed_methods
+            .iter()
+            .filter_map(|method| method.parse().ok())
+            .collect();
+        cors.allow_methods(methods)
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}",
+    tag = "users",
+    params(
+        ("user_id" = String, Path, description = "Public id of the user to fetch")
+    ),
+    responses(
+        (status = 200, description = "User found", body = User),
+        (status = 400, description = "Malformed user id", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+    )
+)]
 async fn users_show(
-    Path(user_id): Path<Uuid>,
-    State(repo): State<DynUserRepo>,
+    _auth: AuthUser,
+    PublicId(user_id): PublicId,
+    State(state): State<SharedState>,
 ) -> Result<Json<User>, AppError> {
-    let user = repo.find(user_id).await?;
+    let user = state.user_repo.find(user_id).await?;
     Ok(user.into())
 }
 
+#[utoipa::path(
+    post,
+    path = "/users",
+    tag = "users",
+    request_body = CreateUser,
+    responses(
+        (status = 200, description = "User created", body = User),
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+    )
+)]
 async fn users_create(
-    State(repo): State<DynUserRepo>,
+    State(state): State<SharedState>,
     Json(params): Json<CreateUser>,
 ) -> Result<Json<User>, AppError> {
-    let user = repo.create(params).await?;
+    let user = state.user_repo.create(params).await?;
     Ok(user.into())
 }
 
-#[derive(Debug)]
+async fn register(
+    State(state): State<SharedState>,
+    Json(params): Json<RegisterUser>,
+) -> Result<Json<User>, AppError> {
+    let salt = SaltString::generate(&mut OsRng);
+    let password_hash = Argon2::default()
+        .hash_password(params.password.as_bytes(), &salt)?
+        .to_string();
+
+    let user = state
+        .user_repo
+        .create_with_password(params.username, password_hash)
+        .await?;
+    Ok(user.into())
+}
+
+async fn login(
+    State(state): State<SharedState>,
+    Json(params): Json<LoginUser>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let user = state.user_repo.find_by_username(&params.username).await?;
+
+    let parsed_hash = PasswordHash::new(&user.password_hash)?;
+    Argon2::default()
+        .verify_password(params.password.as_bytes(), &parsed_hash)
+        .map_err(|_| AppError::Unauthorized)?;
+
+    let exp = (Utc::now() + state.config.jwt_expires_in).timestamp() as usize;
+    let claims = Claims { sub: user.id, exp };
+
+    let token = encode(
+        &JwtHeader::default(),
+        &claims,
+        &EncodingKey::from_secret(state.config.jwt_secret.as_bytes()),
+    )?;
+
+    Ok(Json(json!({ "token": token })))
+}
+
+#[derive(Serialize, ToSchema)]
+struct ErrorResponse {
+    status: u16,
+    message: String,
+}
+
+#[derive(Debug, thiserror::Error)]
 enum AppError {
-    UserRepo(UserRepoError),
+    #[error("resource not found")]
+    NotFound,
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("conflict")]
+    Conflict,
+
+    #[error("internal server error")]
+    Internal(anyhow::Error),
 }
 
 impl From<UserRepoError> for AppError {
     fn from(err: UserRepoError) -> Self {
-        AppError::UserRepo(err)
+        match err {
+            UserRepoError::NotFound => AppError::NotFound,
+            UserRepoError::InvalidUsername => {
+                AppError::Validation("invalid username".to_string())
+            }
+            UserRepoError::Conflict => AppError::Conflict,
+        }
+    }
+}
+
+// Blanket impl so handlers can `?` any error into `AppError::Internal` directly.
+impl<E> From<E> for AppError
+where
+    E: Into<anyhow::Error>,
+{
+    fn from(err: E) -> Self {
+        AppError::Internal(err.into())
     }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        tracing::debug!("AppError error into response {:?}", self);
-        let (status, error_message) = match self {
-            AppError::UserRepo(UserRepoError::NotFound) => {
-                (StatusCode::NOT_FOUND, "user not found")
-            }
-            AppError::UserRepo(UserRepoError::InvalidUsername) => {
-                (StatusCode::BAD_REQUEST, "invalid username")
+        let (status, message) = match &self {
+            AppError::NotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            AppError::Validation(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            AppError::Unauthorized => (StatusCode::UNAUTHORIZED, self.to_string()),
+            AppError::Conflict => (StatusCode::CONFLICT, self.to_string()),
+            AppError::Internal(err) => {
+                tracing::error!("internal error: {err:?}");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
             }
         };
 
-        let body = Json(json!({ "error": error_message }));
+        tracing::debug!("AppError into response: {} {}", status, message);
+
+        let body = Json(json!({ "status": status.as_u16(), "message": message }));
+
+        (status, body).into_response()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Config {
+    bind_addr: String,
+    #[allow(dead_code)]
+    database_url: String,
+    jwt_secret: String,
+    jwt_expires_in: Duration,
+    cors_allowed_origins: Vec<String>,
+    cors_allowed_methods: Vec<String>,
+}
+
+/// Parses env-style durations like `"60m"`, `"12h"`, `"7d"`, `"30s"`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let split = s.len().checked_sub(1)?;
+    let (value, unit) = s.split_at(split);
+    let value: i64 = value.parse().ok()?;
+    match unit {
+        "s" => Some(Duration::seconds(value)),
+        "m" => Some(Duration::minutes(value)),
+        "h" => Some(Duration::hours(value)),
+        "d" => Some(Duration::days(value)),
+        _ => None,
+    }
+}
+
+impl Config {
+    fn init() -> Self {
+        let bind_addr =
+            std::env::var("BIND_ADDR").unwrap_or_else(|_| "127.0.0.1:4444".to_string());
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or_else(|_| "postgres://localhost/app".to_string());
+        let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "dev-secret".to_string());
+        let jwt_expires_in = std::env::var("JWT_EXPIRES_IN")
+            .ok()
+            .and_then(|value| parse_duration(&value))
+            .unwrap_or_else(|| Duration::minutes(60));
+        let cors_allowed_origins = std::env::var("CORS_ALLOWED_ORIGINS")
+            .unwrap_or_else(|_| "*".to_string())
+            .split(',')
+            .map(|origin| origin.trim().to_string())
+            .collect();
+        let cors_allowed_methods = std::env::var("CORS_ALLOWED_METHODS")
+            .unwrap_or_else(|_| "*".to_string())
+            .split(',')
+            .map(|method| method.trim().to_string())
+            .collect();
+
+        Self {
+            bind_addr,
+            database_url,
+            jwt_secret,
+            jwt_expires_in,
+            cors_allowed_origins,
+            cors_allowed_methods,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: Uuid,
+    exp: usize,
+}
+
+struct AuthUser(#[allow(dead_code)] User);
+
+#[async_trait]
+impl FromRequestParts<SharedState> for AuthUser {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &SharedState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = extract_token(parts).ok_or(AuthError::MissingToken)?;
+
+        let claims = decode::<Claims>(
+            &token,
+            &DecodingKey::from_secret(state.config.jwt_secret.as_bytes()),
+            &Validation::default(),
+        )
+        .map_err(|_| AuthError::InvalidToken)?
+        .claims;
+
+        let user = state
+            .user_repo
+            .find(claims.sub)
+            .await
+            .map_err(|_| AuthError::InvalidToken)?;
+
+        Ok(AuthUser(user))
+    }
+}
+
+fn extract_token(parts: &Parts) -> Option<String> {
+    if let Some(value) = parts.headers.get(header::AUTHORIZATION) {
+        let value = value.to_str().ok()?;
+        if let Some(token) = value.strip_prefix("Bearer ") {
+            return Some(token.to_string());
+        }
+    }
+
+    parts
+        .headers
+        .get(header::COOKIE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|cookies| {
+            cookies.split(';').find_map(|cookie| {
+                let mut parts = cookie.trim().splitn(2, '=');
+                let name = parts.next()?;
+                let value = parts.next()?;
+                (name == "token").then(|| value.to_string())
+            })
+        })
+}
+
+#[derive(Debug)]
+enum AuthError {
+    MissingToken,
+    InvalidToken,
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            AuthError::MissingToken => (StatusCode::UNAUTHORIZED, "missing authentication token"),
+            AuthError::InvalidToken => (StatusCode::UNAUTHORIZED, "invalid authentication token"),
+        };
+
+        let body = Json(json!({ "status": status.as_u16(), "message": message }));
 
         (status, body).into_response()
     }
 }
+
+const EXAMPLE_USERNAME: &str = "example";
+const EXAMPLE_PASSWORD: &str = "hunter2";
+
+fn example_user_id() -> Uuid {
+    Uuid::parse_str("bd8197e0-8a30-4e7c-9c93-972fd13ed4c8").expect("valid uuid literal")
+}
+
+fn example_password_hash() -> &'static str {
+    static HASH: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    HASH.get_or_init(|| {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(EXAMPLE_PASSWORD.as_bytes(), &salt)
+            .expect("failed to hash example password")
+            .to_string()
+    })
+}
+
 struct ExampleUserRepo;
 
 #[async_trait]
@@ -96,17 +430,48 @@ impl UserRepo for ExampleUserRepo {
             Ok(User {
                 id,
                 username: "example".to_string(),
+                password_hash: String::new(),
             })
         } else {
             Err(UserRepoError::NotFound)
         }
     }
 
+    async fn find_by_username(&self, username: &str) -> Result<User, UserRepoError> {
+        tracing::debug!("finding user by username {:?}", username);
+        if username != EXAMPLE_USERNAME {
+            return Err(UserRepoError::NotFound);
+        }
+
+        Ok(User {
+            id: example_user_id(),
+            username: EXAMPLE_USERNAME.to_string(),
+            password_hash: example_password_hash().to_string(),
+        })
+    }
+
     async fn create(&self, _params: CreateUser) -> Result<User, UserRepoError> {
         let uuid = Uuid::new_v4();
         Ok(User {
             id: uuid,
             username: "new example".to_string(),
+            password_hash: String::new(),
+        })
+    }
+
+    async fn create_with_password(
+        &self,
+        username: String,
+        password_hash: String,
+    ) -> Result<User, UserRepoError> {
+        if username == EXAMPLE_USERNAME {
+            return Err(UserRepoError::Conflict);
+        }
+
+        Ok(User {
+            id: Uuid::new_v4(),
+            username,
+            password_hash,
         })
     }
 }
@@ -117,21 +482,140 @@ type DynUserRepo = Arc<dyn UserRepo + Send + Sync>;
 trait UserRepo {
     async fn find(&self, id: Uuid) -> Result<User, UserRepoError>;
 
+    async fn find_by_username(&self, username: &str) -> Result<User, UserRepoError>;
+
     async fn create(&self, params: CreateUser) -> Result<User, UserRepoError>;
+
+    async fn create_with_password(
+        &self,
+        username: String,
+        password_hash: String,
+    ) -> Result<User, UserRepoError>;
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct User {
+    #[serde(serialize_with = "serialize_public_id")]
+    #[schema(value_type = String, example = "Ax2f9kLp1q")]
     id: Uuid,
     username: String,
+    #[serde(skip_serializing)]
+    #[schema(ignore)]
+    password_hash: String,
 }
 
-#[derive(Debug, Deserialize)]
+fn serialize_public_id<S>(id: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&PublicId::encode(*id))
+}
+
+const PUBLIC_ID_ALPHABET: &str =
+    "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
+const PUBLIC_ID_MIN_LENGTH: u8 = 10;
+
+fn public_id_sqids() -> &'static Sqids {
+    static SQIDS: std::sync::OnceLock<Sqids> = std::sync::OnceLock::new();
+    SQIDS.get_or_init(|| {
+        Sqids::builder()
+            .alphabet(PUBLIC_ID_ALPHABET.chars().collect())
+            .min_length(PUBLIC_ID_MIN_LENGTH)
+            .build()
+            .expect("PUBLIC_ID_ALPHABET/PUBLIC_ID_MIN_LENGTH must form a valid sqids config")
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PublicId(Uuid);
+
+// sqids itself has no checksum, so `public_id_checksum` mixes in an extra
+// number to catch single-character tampering that would otherwise silently
+// decode to a different id. It's a mixing function, not a keyed MAC.
+fn public_id_checksum(high: u64, low: u64) -> u64 {
+    high.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        .wrapping_add(low)
+        .rotate_left(17)
+}
+
+impl PublicId {
+    fn encode(id: Uuid) -> String {
+        let (high, low) = id.as_u64_pair();
+        let checksum = public_id_checksum(high, low);
+        public_id_sqids()
+            .encode(&[high, low, checksum])
+            .expect("failed to encode public id")
+    }
+}
+
+#[derive(Debug)]
+struct PublicIdError;
+
+impl std::fmt::Display for PublicIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "malformed public id")
+    }
+}
+
+impl std::str::FromStr for PublicId {
+    type Err = PublicIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let numbers = public_id_sqids().decode(s);
+        let [high, low, checksum]: [u64; 3] = numbers.try_into().map_err(|_| PublicIdError)?;
+        if checksum != public_id_checksum(high, low) {
+            return Err(PublicIdError);
+        }
+        Ok(PublicId(Uuid::from_u64_pair(high, low)))
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<SharedState> for PublicId {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &SharedState,
+    ) -> Result<Self, Self::Rejection> {
+        let Path(raw) = Path::<String>::from_request_parts(parts, state)
+            .await
+            .map_err(|_| AppError::Validation("malformed user id".to_string()))?;
+        raw.parse()
+            .map_err(|_: PublicIdError| AppError::Validation("malformed user id".to_string()))
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 #[allow(dead_code)]
 struct CreateUser {
     username: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct RegisterUser {
+    username: String,
+    password: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct LoginUser {
+    username: String,
+    password: String,
+}
+
 #[derive(Debug)]
 enum UserRepoError {
     #[allow(dead_code)]
@@ -139,6 +623,8 @@ enum UserRepoError {
 
     #[allow(dead_code)]
     InvalidUsername,
+
+    Conflict,
 }
 
 #[cfg(test)]
@@ -155,15 +641,49 @@ mod tests {
     // use tower::Service; // for `call`
     use tower::ServiceExt;
 
+    fn test_config() -> Config {
+        Config {
+            bind_addr: "127.0.0.1:0".to_string(),
+            database_url: "postgres://localhost/test".to_string(),
+            jwt_secret: "test-secret".to_string(),
+            jwt_expires_in: Duration::minutes(60),
+            cors_allowed_origins: vec!["*".to_string()],
+            cors_allowed_methods: vec!["*".to_string()],
+        }
+    }
+
+    fn test_state() -> SharedState {
+        Arc::new(AppState {
+            config: test_config(),
+            user_repo: Arc::new(ExampleUserRepo) as DynUserRepo,
+        })
+    }
+
+    fn token_for(sub: Uuid) -> String {
+        let claims = Claims {
+            sub,
+            exp: (Utc::now() + Duration::minutes(60)).timestamp() as usize,
+        };
+        encode(
+            &JwtHeader::default(),
+            &claims,
+            &EncodingKey::from_secret(b"test-secret"),
+        )
+        .unwrap()
+    }
+
     #[tokio::test]
     async fn test_users_show_found() {
-        let user_repo = Arc::new(ExampleUserRepo) as DynUserRepo;
-        let app = app(user_repo);
+        let app = app(test_state());
+
+        let found_id = Uuid::parse_str("bd8197e0-8a30-4e7c-9c93-972fd13ed4c8").unwrap();
+        let token = token_for(found_id);
 
         let response = app
             .oneshot(
                 Request::builder()
-                    .uri("/users/bd8197e0-8a30-4e7c-9c93-972fd13ed4c8")
+                    .uri(format!("/users/{}", PublicId::encode(found_id)))
+                    .header(http::header::AUTHORIZATION, format!("Bearer {token}"))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -175,13 +695,17 @@ mod tests {
 
     #[tokio::test]
     async fn test_users_show_not_found() {
-        let user_repo = Arc::new(ExampleUserRepo) as DynUserRepo;
-        let app = app(user_repo);
+        let app = app(test_state());
+
+        let found_id = Uuid::parse_str("bd8197e0-8a30-4e7c-9c93-972fd13ed4c8").unwrap();
+        let not_found_id = Uuid::parse_str("ad8197e0-8a30-4e7c-9c93-972fd13ed4c8").unwrap();
+        let token = token_for(found_id);
 
         let response = app
             .oneshot(
                 Request::builder()
-                    .uri("/users/ad8197e0-8a30-4e7c-9c93-972fd13ed4c8")
+                    .uri(format!("/users/{}", PublicId::encode(not_found_id)))
+                    .header(http::header::AUTHORIZATION, format!("Bearer {token}"))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -189,12 +713,96 @@ mod tests {
             .unwrap();
 
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.get("status").and_then(Value::as_u64), Some(404));
+        assert_eq!(
+            body.get("message").and_then(Value::as_str),
+            Some("resource not found")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_users_show_unauthorized() {
+        let app = app(test_state());
+
+        let found_id = Uuid::parse_str("bd8197e0-8a30-4e7c-9c93-972fd13ed4c8").unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/users/{}", PublicId::encode(found_id)))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_users_show_malformed_public_id() {
+        let app = app(test_state());
+
+        let found_id = Uuid::parse_str("bd8197e0-8a30-4e7c-9c93-972fd13ed4c8").unwrap();
+        let token = token_for(found_id);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/users/not-a-valid-public-id!!")
+                    .header(http::header::AUTHORIZATION, format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.get("status").and_then(Value::as_u64), Some(400));
+        assert_eq!(
+            body.get("message").and_then(Value::as_str),
+            Some("malformed user id")
+        );
+    }
+
+    #[test]
+    fn test_public_id_round_trip() {
+        let id = Uuid::parse_str("bd8197e0-8a30-4e7c-9c93-972fd13ed4c8").unwrap();
+        let token = PublicId::encode(id);
+
+        let decoded: PublicId = token.parse().unwrap();
+        assert_eq!(decoded.0, id);
+    }
+
+    #[test]
+    fn test_public_id_rejects_tampered_token() {
+        let id = Uuid::parse_str("bd8197e0-8a30-4e7c-9c93-972fd13ed4c8").unwrap();
+        let original = PublicId::encode(id);
+
+        // Flipping any single character invalidates the embedded checksum,
+        // even though sqids itself happily decodes the tampered string.
+        for i in 0..original.len() {
+            let mut token = original.clone().into_bytes();
+            token[i] = if token[i] == b'a' { b'b' } else { b'a' };
+            let tampered = String::from_utf8(token).unwrap();
+
+            let decoded: Result<PublicId, _> = tampered.parse();
+            assert!(
+                decoded.is_err(),
+                "tampering with byte {i} of {original:?} was not rejected"
+            );
+        }
     }
 
     #[tokio::test]
     async fn test_users_create() {
-        let user_repo = Arc::new(ExampleUserRepo) as DynUserRepo;
-        let app = app(user_repo);
+        let app = app(test_state());
 
         let response = app
             .oneshot(
@@ -214,11 +822,129 @@ mod tests {
         let body: Value = serde_json::from_slice(&body).unwrap();
         let id = body.get("id").unwrap().as_str().unwrap();
 
-        assert_eq!(id.len(), 36);
+        assert!(id.len() >= PUBLIC_ID_MIN_LENGTH as usize);
+        let _: PublicId = id.parse().unwrap();
         let name = body
             .get("username")
             .and_then(Value::as_str)
             .unwrap_or("no name");
         assert_eq!(name, "new example");
     }
+
+    #[tokio::test]
+    async fn test_register() {
+        let app = app(test_state());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/register")
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        r#"{"username":"new-user","password":"hunter2"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert!(body.get("password_hash").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_register_conflict() {
+        let app = app(test_state());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/register")
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        r#"{"username":"example","password":"hunter2"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_login_success() {
+        let app = app(test_state());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/login")
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        r#"{"username":"example","password":"hunter2"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert!(body.get("token").and_then(Value::as_str).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_login_wrong_password() {
+        let app = app(test_state());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/login")
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        r#"{"username":"example","password":"not-the-password"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_openapi_json_is_gzip_compressed() {
+        let app = app(test_state());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api-docs/openapi.json")
+                    .header(http::header::ACCEPT_ENCODING, "gzip")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .headers()
+                .get(http::header::CONTENT_ENCODING)
+                .and_then(|value| value.to_str().ok()),
+            Some("gzip")
+        );
+    }
 }